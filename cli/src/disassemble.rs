@@ -0,0 +1,283 @@
+use model::class::{ClassAttribute, ClassConstant, ClassMethod, JvmClass};
+use vm::opcode;
+
+/// Renders `class` as a `javap`-style textual listing: the class header,
+/// the constant pool, each field/method with its descriptor, and for every
+/// `Code` attribute a per-instruction disassembly followed by its exception
+/// table and line-number table. Modeled on Krakatau's disassembler output
+/// so the result round-trips conceptually back to bytecode.
+pub fn disassemble(class: &JvmClass) -> String {
+    let mut out = String::new();
+
+    out.push_str(&format!(
+        "class {} extends {}\n",
+        class.this_class,
+        class.super_class.as_deref().unwrap_or("java/lang/Object")
+    ));
+    out.push_str(&format!("  flags: {:?}\n", class.access_flags));
+    out.push_str(&format!(
+        "  version: {}.{}\n\n",
+        class.version.major, class.version.minor
+    ));
+
+    out.push_str("Constant pool:\n");
+    for (index, constant) in class.constants.iter().enumerate() {
+        if let ClassConstant::Unused() = constant {
+            continue;
+        }
+        out.push_str(&format!("  #{} = {}\n", index, describe_constant(constant)));
+    }
+    out.push('\n');
+
+    for field in &class.fields {
+        out.push_str(&format!(
+            "field {} {:?} {}\n",
+            field.name, field.access_flags, field.descriptor
+        ));
+    }
+    out.push('\n');
+
+    for method in &class.methods {
+        out.push_str(&format!(
+            "method {} {:?} {}\n",
+            method.name, method.access_flags, method.descriptor
+        ));
+        disassemble_method(&mut out, class, method);
+        out.push('\n');
+    }
+
+    out
+}
+
+fn disassemble_method(out: &mut String, class: &JvmClass, method: &ClassMethod) {
+    let code = method.attributes.iter().find_map(|attribute| match attribute {
+        ClassAttribute::Code(code) => Some(code),
+        _ => None,
+    });
+    let Some(code) = code else { return };
+
+    out.push_str(&format!(
+        "  max_stack={} max_locals={}\n",
+        code.max_stack, code.max_locals
+    ));
+
+    let mut pc = 0usize;
+    while pc < code.code.len() {
+        let opcode_byte = code.code[pc];
+        let (mnemonic, length) = match opcode::lookup(opcode_byte) {
+            Some(info) => (info.mnemonic, info.length as usize),
+            None => ("<unknown>", 1),
+        };
+
+        let operands = decode_operands(class, &code.code, pc, opcode_byte, length);
+        out.push_str(&format!("    {}: {}{}\n", pc, mnemonic, operands));
+
+        pc += length;
+    }
+
+    if !code.exception_table.is_empty() {
+        out.push_str("  Exception table:\n");
+        out.push_str("     from    to  target  type\n");
+        for entry in &code.exception_table {
+            out.push_str(&format!(
+                "    {:>7} {:>5} {:>7}  {}\n",
+                entry.start_pc,
+                entry.end_pc,
+                entry.handler_pc,
+                entry.catch_type.as_deref().unwrap_or("any")
+            ));
+        }
+    }
+
+    for attribute in &code.attributes {
+        if let ClassAttribute::LineNumberTable(lines) = attribute {
+            out.push_str("  LineNumberTable:\n");
+            for line in lines {
+                out.push_str(&format!("    pc {}: line {}\n", line.start_pc, line.line_number));
+            }
+        }
+    }
+}
+
+/// Decodes the operands of a constant-pool-indexed instruction (`ldc`,
+/// `getstatic`, `invokevirtual`, ...) into its target's textual form, or a
+/// raw list of the operand bytes (in decimal) for anything else.
+fn decode_operands(class: &JvmClass, code: &[u8], pc: usize, opcode_byte: u8, length: usize) -> String {
+    let cpool_index = match opcode_byte {
+        18 => Some(code[pc + 1] as u16),
+        19 | 20 | 178 | 179 | 180 | 181 | 182 | 183 | 184 | 185 | 186 | 187 | 189 | 192 | 193 if length >= 3 => {
+            Some(((code[pc + 1] as u16) << 8) | code[pc + 2] as u16)
+        }
+        _ => None,
+    };
+
+    if let Some(index) = cpool_index {
+        return format!(
+            " #{} // {}",
+            index,
+            class
+                .constants
+                .get(index as usize)
+                .map(describe_constant)
+                .unwrap_or_else(|| "<invalid>".to_string())
+        );
+    }
+
+    let raw_operands = &code[pc + 1..pc + length];
+    if raw_operands.is_empty() {
+        String::new()
+    } else {
+        format!(" {:?}", raw_operands)
+    }
+}
+
+fn describe_constant(constant: &ClassConstant) -> String {
+    match constant {
+        ClassConstant::Unused() => "Unused".to_string(),
+        ClassConstant::Class(name) => format!("Class {}", name),
+        ClassConstant::Fieldref(class_name, field_name, descriptor) => {
+            format!("Fieldref {}.{}:{}", class_name, field_name, descriptor)
+        }
+        ClassConstant::Methodref(class_name, method_name, descriptor) => {
+            format!("Methodref {}.{}:{}", class_name, method_name, descriptor)
+        }
+        ClassConstant::InterfaceMethodref(class_name, method_name, descriptor) => {
+            format!("InterfaceMethodref {}.{}:{}", class_name, method_name, descriptor)
+        }
+        ClassConstant::String(value) => format!("String {:?}", value),
+        ClassConstant::Integer(value) => format!("Integer {}", value),
+        ClassConstant::Float(value) => format!("Float {}", value),
+        ClassConstant::Long(value) => format!("Long {}", value),
+        ClassConstant::Double(value) => format!("Double {}", value),
+        ClassConstant::MethodNameAndType(name, descriptor) => {
+            format!("NameAndType {}:{}", name, descriptor)
+        }
+        ClassConstant::FieldNameAndType(name, descriptor) => {
+            format!("NameAndType {}:{}", name, descriptor)
+        }
+        ClassConstant::Utf8(value) => format!("Utf8 {:?}", value),
+        ClassConstant::MethodHandle(reference_kind, reference_index) => {
+            format!("MethodHandle {:?} #{}", reference_kind, reference_index)
+        }
+        ClassConstant::MethodType(descriptor) => format!("MethodType {}", descriptor),
+        ClassConstant::Dynamic(bootstrap_index, name, descriptor) => {
+            format!("Dynamic #{}:{}:{}", bootstrap_index, name, descriptor)
+        }
+        ClassConstant::InvokeDynamic(bootstrap_index, name, descriptor) => {
+            format!("InvokeDynamic #{}:{}:{}", bootstrap_index, name, descriptor)
+        }
+        ClassConstant::NotImplemented => "NotImplemented".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use model::class::{Code, MethodSignature, TypeSignature};
+
+    #[test]
+    fn disassembles_a_method_body_with_a_cpool_indexed_operand() {
+        let mut class = JvmClass::default();
+        class.this_class = "Example".to_string();
+        class.constants = vec![
+            ClassConstant::Unused(),
+            ClassConstant::Methodref(
+                "java/lang/Object".to_string(),
+                "<init>".to_string(),
+                MethodSignature {
+                    parameters: vec![],
+                    return_type: TypeSignature::Void,
+                },
+            ),
+        ];
+        class.methods = vec![ClassMethod {
+            name: "<init>".to_string(),
+            attributes: vec![ClassAttribute::Code(Code {
+                max_stack: 1,
+                max_locals: 1,
+                code: vec![184, 0, 1, 177], // invokestatic #1, return
+                exception_table: vec![],
+                attributes: vec![],
+            })],
+            ..ClassMethod::default()
+        }];
+
+        let output = disassemble(&class);
+
+        assert!(output.contains("class Example extends java/lang/Object"));
+        assert!(output.contains("0: invokestatic #1 // Methodref java/lang/Object.<init>:()V"));
+        assert!(output.contains("3: return"));
+    }
+
+    #[test]
+    fn unknown_opcodes_are_rendered_as_a_placeholder_mnemonic() {
+        let mut class = JvmClass::default();
+        class.methods = vec![ClassMethod {
+            name: "odd".to_string(),
+            attributes: vec![ClassAttribute::Code(Code {
+                max_stack: 0,
+                max_locals: 0,
+                code: vec![253],
+                exception_table: vec![],
+                attributes: vec![],
+            })],
+            ..ClassMethod::default()
+        }];
+
+        let output = disassemble(&class);
+
+        assert!(output.contains("0: <unknown>"));
+    }
+
+    #[test]
+    fn invokeinterface_resolves_its_cpool_index_like_invokedynamic_does() {
+        let mut class = JvmClass::default();
+        class.constants = vec![
+            ClassConstant::Unused(),
+            ClassConstant::InterfaceMethodref(
+                "java/util/List".to_string(),
+                "add".to_string(),
+                MethodSignature {
+                    parameters: vec![],
+                    return_type: TypeSignature::Boolean,
+                },
+            ),
+        ];
+        class.methods = vec![ClassMethod {
+            name: "run".to_string(),
+            attributes: vec![ClassAttribute::Code(Code {
+                max_stack: 2,
+                max_locals: 1,
+                code: vec![185, 0, 1, 1, 0], // invokeinterface #1, count=1, 0
+                exception_table: vec![],
+                attributes: vec![],
+            })],
+            ..ClassMethod::default()
+        }];
+
+        let output = disassemble(&class);
+
+        assert!(output.contains("0: invokeinterface #1 // InterfaceMethodref java/util/List.add:()Z"));
+    }
+
+    #[test]
+    fn ldc_resolves_its_one_byte_cpool_index() {
+        let mut class = JvmClass::default();
+        class.constants = vec![ClassConstant::Unused(), ClassConstant::String("hi".to_string())];
+        class.methods = vec![ClassMethod {
+            name: "run".to_string(),
+            attributes: vec![ClassAttribute::Code(Code {
+                max_stack: 1,
+                max_locals: 0,
+                code: vec![18, 1], // ldc #1
+                exception_table: vec![],
+                attributes: vec![],
+            })],
+            ..ClassMethod::default()
+        }];
+
+        let output = disassemble(&class);
+
+        assert!(output.contains(r#"0: ldc #1 // String "hi""#));
+    }
+}
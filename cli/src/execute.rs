@@ -2,6 +2,7 @@ use std::path::PathBuf;
 
 use loader::CompositeLoader;
 use model::vm::VmThread;
+use vm::verify::verify_method;
 use vm::vm_thread::VmTheadImpl;
 use rt::bootstrap_vm;
 
@@ -18,6 +19,19 @@ pub fn run(main_class: String, class_paths: Vec<PathBuf>) {
         CompositeLoader::open(vec![Box::new(runtime_classloader), Box::new(classloader)]);
 
     let vm = bootstrap_vm(classloader);
+
+    // No parser in this tree populates ClassAttribute::StackMapTable yet, so
+    // verify_method has no declared frames to check against any real class
+    // and this loop is currently a no-op other than the max_locals check on
+    // the entry frame. Left wired in so it starts verifying for free once a
+    // parser produces StackMapTable attributes.
+    let main = vm.class_loader.load(&main_class).unwrap();
+    for method in &main.methods {
+        if let Err(error) = verify_method(&main, method) {
+            panic!("{}.{}: {}", main_class, method.name, error);
+        }
+    }
+
     VmThread::new(&vm, "Thread-0".to_string()).invoke_method(
         &main_class,
         &MAIN_METHOD_NAME.to_string(),
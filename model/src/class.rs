@@ -65,7 +65,7 @@ pub enum ClassConstant {
 
     // reference_kind, reference_index
     // See https://docs.oracle.com/javase/specs/jvms/se7/html/jvms-5.html#jvms-5.4.3.5
-    // MethodHandle(u8, u16),
+    MethodHandle(ReferenceKind, u16),
 
     // descriptor_index
     MethodType(MethodSignature),
@@ -79,6 +79,41 @@ pub enum ClassConstant {
     NotImplemented,
 }
 
+/// The `reference_kind` of a `CONSTANT_MethodHandle_info`, identifying how
+/// `reference_index` should be resolved.
+/// See https://docs.oracle.com/javase/specs/jvms/se7/html/jvms-5.html#jvms-5.4.3.5
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReferenceKind {
+    GetField,
+    GetStatic,
+    PutField,
+    PutStatic,
+    InvokeVirtual,
+    InvokeStatic,
+    InvokeSpecial,
+    NewInvokeSpecial,
+    InvokeInterface,
+}
+
+impl TryFrom<u8> for ReferenceKind {
+    type Error = u8;
+
+    fn try_from(tag: u8) -> Result<ReferenceKind, u8> {
+        match tag {
+            1 => Ok(ReferenceKind::GetField),
+            2 => Ok(ReferenceKind::GetStatic),
+            3 => Ok(ReferenceKind::PutField),
+            4 => Ok(ReferenceKind::PutStatic),
+            5 => Ok(ReferenceKind::InvokeVirtual),
+            6 => Ok(ReferenceKind::InvokeStatic),
+            7 => Ok(ReferenceKind::InvokeSpecial),
+            8 => Ok(ReferenceKind::NewInvokeSpecial),
+            9 => Ok(ReferenceKind::InvokeInterface),
+            other => Err(other),
+        }
+    }
+}
+
 #[derive(EnumSetType, Debug)]
 pub enum ClassAccessFlag {
     Public,
@@ -196,9 +231,42 @@ pub enum ClassAttribute {
     Exceptions(Vec<u16>),
     ConstantValue(ClassConstant),
     BootstrapMethods(Vec<BootstrapMethod>),
+    StackMapTable(Vec<StackMapFrame>),
     NotImplemented,
 }
 
+/// One entry of a `StackMapTable` attribute: the verification types of the
+/// locals and operand stack the verifier must find (or be assignable from)
+/// at bytecode offset `offset_delta` for the first frame, and at
+/// `offset_delta + 1` bytes past the previous frame's offset for each later
+/// frame.
+#[derive(Default, Clone, Debug)]
+pub struct StackMapFrame {
+    pub offset_delta: u16,
+    pub locals: Vec<VerificationType>,
+    pub stack: Vec<VerificationType>,
+}
+
+/// A JVMS verification type, as carried by `StackMapFrame` locals/stack
+/// entries. `Long` and `Double` each occupy two local/stack slots, same as
+/// their runtime representation.
+#[derive(Clone, Debug, PartialEq)]
+pub enum VerificationType {
+    Top,
+    Integer,
+    Float,
+    Long,
+    Double,
+    Null,
+    UninitializedThis,
+    /// Names the class (by its `Class` constant) a reference must be an
+    /// instance of.
+    Object(String),
+    /// A reference produced by a `new` at the given bytecode offset that
+    /// has not yet had its `<init>` invoked.
+    Uninitialized(u16),
+}
+
 #[derive(Default, Clone, Debug)]
 pub struct Code {
     pub max_stack: u16,
@@ -251,6 +319,33 @@ impl Default for TypeSignature {
     }
 }
 
+impl TypeSignature {
+    /// Parses a single field descriptor, e.g. `I`, `Ljava/lang/String;` or
+    /// `[[D`. `Class` constant pool entries for array types are stored in
+    /// this same descriptor form, so this is how `anewarray`/`multianewarray`
+    /// recover a `TypeSignature` from them.
+    pub fn from_descriptor(descriptor: &str) -> TypeSignature {
+        let mut chars = descriptor.chars();
+        match chars.next().expect("empty type descriptor") {
+            'V' => TypeSignature::Void,
+            'Z' => TypeSignature::Boolean,
+            'B' => TypeSignature::Byte,
+            'C' => TypeSignature::Char,
+            'S' => TypeSignature::Short,
+            'I' => TypeSignature::Int,
+            'J' => TypeSignature::Long,
+            'F' => TypeSignature::Float,
+            'D' => TypeSignature::Double,
+            'L' => {
+                let class_path = chars.as_str().trim_end_matches(';');
+                TypeSignature::Class(class_path.to_string())
+            }
+            '[' => TypeSignature::Array(Box::new(TypeSignature::from_descriptor(chars.as_str()))),
+            other => panic!("invalid type descriptor: {}{}", other, chars.as_str()),
+        }
+    }
+}
+
 #[derive(Default, Clone, Debug)]
 pub struct MethodSignature {
     pub parameters: Vec<TypeSignature>,
@@ -0,0 +1,33 @@
+use model::class::TypeSignature;
+
+use crate::VmPrimitive;
+
+/// A heap-allocated Java array. `atype` carries the `newarray` primitive
+/// type code (4-11) for primitive arrays and is `None` for reference
+/// arrays; `dastore` type-checks against `atype`. `element_type` records
+/// the full `TypeSignature` of a single element, which also describes
+/// reference arrays.
+#[derive(Debug)]
+pub struct VmArray {
+    pub atype: Option<u8>,
+    pub element_type: TypeSignature,
+    pub elements: Vec<VmPrimitive>,
+}
+
+impl VmArray {
+    pub fn new_primitive(atype: u8, element_type: TypeSignature, length: usize, fill: VmPrimitive) -> VmArray {
+        VmArray {
+            atype: Some(atype),
+            element_type,
+            elements: vec![fill; length],
+        }
+    }
+
+    pub fn new_reference(element_type: TypeSignature, length: usize) -> VmArray {
+        VmArray {
+            atype: None,
+            element_type,
+            elements: vec![VmPrimitive::Null; length],
+        }
+    }
+}
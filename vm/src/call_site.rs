@@ -0,0 +1,19 @@
+use model::class::MethodSignature;
+
+/// A resolved, invocable target produced by evaluating a `MethodHandle`
+/// constant -- the eventual target of a `CallSite`.
+#[derive(Clone, Debug)]
+pub struct VmMethodHandle {
+    pub class_name: String,
+    pub method_name: String,
+    pub method_signature: MethodSignature,
+}
+
+/// The outcome of bootstrapping an `invokedynamic` call site. Cached per
+/// call site (by declaring class and constant-pool index) so re-executing
+/// the same instruction dispatches straight to `target` instead of
+/// re-running the bootstrap method.
+#[derive(Clone, Debug)]
+pub struct CallSite {
+    pub target: VmMethodHandle,
+}
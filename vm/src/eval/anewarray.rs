@@ -0,0 +1,41 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use model::class::{ClassConstant, TypeSignature};
+
+use crate::array::VmArray;
+use crate::exception::throw;
+use crate::{frame::VmFrameImpl, VmPrimitive, VmThread};
+
+/// `anewarray`: pops a count off the stack, reads the two-byte operand as a
+/// `Class` constant (holding either a plain class name or an array
+/// descriptor), and allocates a one-dimensional reference array of that
+/// count, each slot initialized to `Null`.
+pub fn eval(vm_thread: &mut VmThread, pc: u16) -> Option<u16> {
+    let frame = vm_thread.frame_stack.last_mut().unwrap();
+    let count = frame.stack_pop_int();
+    let indexbyte1 = (*frame.code.code.get((pc + 1) as usize).unwrap() as u16) << 8;
+    let indexbyte2 = *frame.code.code.get((pc + 2) as usize).unwrap() as u16;
+    let index = indexbyte1 + indexbyte2;
+    let class_name = frame.class_name.clone();
+
+    if count < 0 {
+        let exception = vm_thread.vm.new_exception("java/lang/NegativeArraySizeException", None);
+        return throw(vm_thread, pc, exception).resume_here();
+    }
+
+    let class = vm_thread.vm.class_loader.load(&class_name).unwrap();
+    let component_type = match class.constants.get(index as usize) {
+        Some(ClassConstant::Class(name)) if name.starts_with('[') => TypeSignature::from_descriptor(name),
+        Some(ClassConstant::Class(name)) => TypeSignature::Class(name.clone()),
+        other => panic!("anewarray: expected a Class constant, got {:?}", other),
+    };
+
+    trace!("anewarray: allocating {}[{}]", component_type, count);
+
+    let array = VmArray::new_reference(component_type, count as usize);
+    let frame = vm_thread.frame_stack.last_mut().unwrap();
+    frame.stack_push_reference(VmPrimitive::ArrayReference(Rc::new(RefCell::new(array))));
+
+    Some(pc + 3)
+}
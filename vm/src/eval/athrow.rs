@@ -0,0 +1,20 @@
+use crate::exception::throw;
+use crate::{frame::VmFrameImpl, VmPrimitive, VmThread};
+
+pub fn eval(vm_thread: &mut VmThread, pc: u16) -> Option<u16> {
+    let frame = vm_thread.frame_stack.last_mut().unwrap();
+    let exception = frame.stack_pop_reference();
+
+    match exception {
+        VmPrimitive::Reference(exception_ref) => {
+            trace!("athrow: throwing {:?}", exception_ref);
+            throw(vm_thread, pc, exception_ref).resume_here()
+        }
+        VmPrimitive::Null => {
+            trace!("athrow: null reference thrown, raising NullPointerException instead");
+            let npe = vm_thread.vm.new_exception("java/lang/NullPointerException", None);
+            throw(vm_thread, pc, npe).resume_here()
+        }
+        other => panic!("athrow: expected a reference on the stack, got {:?}", other),
+    }
+}
@@ -0,0 +1,153 @@
+use model::class::{ClassAttribute, ClassConstant};
+
+use crate::call_site::{CallSite, VmMethodHandle};
+use crate::{frame::VmFrameImpl, VmThread};
+
+pub fn eval(vm_thread: &mut VmThread, pc: u16) -> Option<u16> {
+    let frame = vm_thread.frame_stack.last().unwrap();
+    let class_name = frame.class_name.clone();
+    let indexbyte1 = (*frame.code.code.get((pc + 1) as usize).unwrap() as u16) << 8;
+    let indexbyte2 = *frame.code.code.get((pc + 2) as usize).unwrap() as u16;
+    let index = indexbyte1 + indexbyte2;
+
+    let cache_key = (class_name.clone(), index);
+    let cached = vm_thread.vm.call_site_cache.borrow().get(&cache_key).cloned();
+    let call_site = match cached {
+        Some(call_site) => call_site,
+        None => {
+            let call_site = bootstrap_call_site(vm_thread, &class_name, index);
+            vm_thread
+                .vm
+                .call_site_cache
+                .borrow_mut()
+                .insert(cache_key, call_site.clone());
+            call_site
+        }
+    };
+
+    trace!(
+        "invokedynamic: dispatching to {}.{}{}",
+        call_site.target.class_name,
+        call_site.target.method_name,
+        call_site.target.method_signature
+    );
+
+    // The call site's arguments are already on the operand stack in the
+    // order invoke_method expects; leave them there for it to consume.
+    vm_thread.invoke_method(
+        &call_site.target.class_name,
+        &call_site.target.method_name,
+        &call_site.target.method_signature.to_string(),
+        false,
+    );
+
+    Some(pc + 5)
+}
+
+fn bootstrap_call_site(vm_thread: &mut VmThread, class_name: &str, index: u16) -> CallSite {
+    let class = vm_thread.vm.class_loader.load(class_name).unwrap();
+
+    let (bootstrap_index, _name, method_signature) = match class.constants.get(index as usize) {
+        Some(ClassConstant::InvokeDynamic(bootstrap_index, name, signature)) => {
+            (*bootstrap_index, name.clone(), signature.clone())
+        }
+        other => panic!("invokedynamic: expected an InvokeDynamic constant, got {:?}", other),
+    };
+
+    let bootstrap_methods = class.attributes.iter().find_map(|attribute| match attribute {
+        ClassAttribute::BootstrapMethods(methods) => Some(methods),
+        _ => None,
+    });
+    let bootstrap_method = bootstrap_methods
+        .and_then(|methods| methods.get(bootstrap_index as usize))
+        .expect("invokedynamic: class is missing its BootstrapMethods attribute");
+
+    let method_handle = match class.constants.get(bootstrap_method.method_ref as usize) {
+        Some(ClassConstant::MethodHandle(reference_kind, reference_index)) => {
+            resolve_method_handle(&class, *reference_kind, *reference_index)
+        }
+        other => panic!("invokedynamic: bootstrap method_ref is not a MethodHandle: {:?}", other),
+    };
+
+    let static_arguments: Vec<_> = bootstrap_method
+        .arguments
+        .iter()
+        .map(|argument_index| class.constants.get(*argument_index as usize).unwrap().clone())
+        .collect();
+
+    vm_thread.vm.invoke_bootstrap_method(&method_handle, method_signature, static_arguments)
+}
+
+/// Resolves the `MethodHandle` that is `bootstrap_method.method_ref` -- not
+/// any `MethodHandle` constant in general. JVMS requires a bootstrap method
+/// handle to always invoke a method, so `reference_kind` here is always one
+/// of InvokeVirtual/InvokeStatic/InvokeSpecial/NewInvokeSpecial/
+/// InvokeInterface (5-9); kinds 1-4 (get/putField, get/putStatic) only ever
+/// show up as static arguments or elsewhere, never as the bootstrap handle
+/// itself, so they aren't handled here.
+fn resolve_method_handle(
+    class: &model::class::JvmClass,
+    reference_kind: model::class::ReferenceKind,
+    reference_index: u16,
+) -> VmMethodHandle {
+    match class.constants.get(reference_index as usize) {
+        Some(ClassConstant::Methodref(class_name, method_name, method_signature))
+        | Some(ClassConstant::InterfaceMethodref(class_name, method_name, method_signature)) => {
+            VmMethodHandle {
+                class_name: class_name.clone(),
+                method_name: method_name.clone(),
+                method_signature: method_signature.clone(),
+            }
+        }
+        other => panic!(
+            "invokedynamic: bootstrap MethodHandle({:?}, ..) does not reference a method: {:?}",
+            reference_kind, other
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use model::class::{JvmClass, MethodSignature, ReferenceKind, TypeSignature};
+
+    #[test]
+    fn resolves_a_methodref_reference() {
+        let signature = MethodSignature {
+            parameters: vec![],
+            return_type: TypeSignature::Void,
+        };
+        let mut class = JvmClass::default();
+        class.constants = vec![
+            ClassConstant::Unused(),
+            ClassConstant::Methodref("java/lang/Runnable".to_string(), "run".to_string(), signature),
+        ];
+
+        let handle = resolve_method_handle(&class, ReferenceKind::InvokeInterface, 1);
+
+        assert_eq!(handle.class_name, "java/lang/Runnable");
+        assert_eq!(handle.method_name, "run");
+    }
+
+    #[test]
+    fn resolves_an_interface_methodref_reference() {
+        let signature = MethodSignature {
+            parameters: vec![],
+            return_type: TypeSignature::Int,
+        };
+        let mut class = JvmClass::default();
+        class.constants = vec![
+            ClassConstant::Unused(),
+            ClassConstant::InterfaceMethodref(
+                "java/util/Comparator".to_string(),
+                "compare".to_string(),
+                signature,
+            ),
+        ];
+
+        let handle = resolve_method_handle(&class, ReferenceKind::InvokeInterface, 1);
+
+        assert_eq!(handle.class_name, "java/util/Comparator");
+        assert_eq!(handle.method_name, "compare");
+    }
+}
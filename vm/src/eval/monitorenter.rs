@@ -1,15 +1,24 @@
-use vm::primitive::Primitive;
-use vm::Vm;
+use crate::exception::throw;
+use crate::{frame::VmFrameImpl, VmPrimitive, VmThread};
 
-pub fn eval(vm: &Vm, pc: u16) -> Option<u16> {
-    let frame = vm.frame_stack.last_mut().unwrap();
+pub fn eval(vm_thread: &mut VmThread, pc: u16) -> Option<u16> {
+    let frame = vm_thread.frame_stack.last_mut().unwrap();
     let objectref = frame.stack_pop_reference();
-    match objectref {
-        Primitive::Null => panic!("Not implemented -> throw NullPointerException"),
-        _ => (),
+    let object = match objectref {
+        VmPrimitive::Null => {
+            let npe = vm_thread.vm.new_exception("java/lang/NullPointerException", None);
+            return throw(vm_thread, pc, npe).resume_here();
+        }
+        VmPrimitive::Reference(object) => object,
+        other => panic!("monitorenter: expected a reference on the stack, got {:?}", other),
     };
 
-    trace!("monitorenter: Popped one reference from stack and did nothing else");
+    trace!("monitorenter: {} entering monitor on {:?}", vm_thread.name, object);
+    object
+        .borrow()
+        .monitor
+        .enter(&vm_thread.name)
+        .expect("monitor contention across VmThreads requires an Arc<Mutex<..>> heap (not yet implemented)");
 
     Some(pc + 1)
 }
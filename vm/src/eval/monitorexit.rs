@@ -0,0 +1,26 @@
+use crate::exception::throw;
+use crate::{frame::VmFrameImpl, VmPrimitive, VmThread};
+
+pub fn eval(vm_thread: &mut VmThread, pc: u16) -> Option<u16> {
+    let frame = vm_thread.frame_stack.last_mut().unwrap();
+    let objectref = frame.stack_pop_reference();
+    let object = match objectref {
+        VmPrimitive::Null => {
+            let npe = vm_thread.vm.new_exception("java/lang/NullPointerException", None);
+            return throw(vm_thread, pc, npe).resume_here();
+        }
+        VmPrimitive::Reference(object) => object,
+        other => panic!("monitorexit: expected a reference on the stack, got {:?}", other),
+    };
+
+    trace!("monitorexit: {} exiting monitor on {:?}", vm_thread.name, object);
+
+    if object.borrow().monitor.exit(&vm_thread.name).is_err() {
+        let exception = vm_thread
+            .vm
+            .new_exception("java/lang/IllegalMonitorStateException", None);
+        return throw(vm_thread, pc, exception).resume_here();
+    }
+
+    Some(pc + 1)
+}
@@ -0,0 +1,120 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use model::class::{ClassConstant, TypeSignature};
+
+use crate::array::VmArray;
+use crate::exception::throw;
+use crate::{frame::VmFrameImpl, VmPrimitive, VmThread};
+
+pub fn eval(vm_thread: &mut VmThread, pc: u16) -> Option<u16> {
+    let frame = vm_thread.frame_stack.last_mut().unwrap();
+    let indexbyte1 = (*frame.code.code.get((pc + 1) as usize).unwrap() as u16) << 8;
+    let indexbyte2 = *frame.code.code.get((pc + 2) as usize).unwrap() as u16;
+    let index = indexbyte1 + indexbyte2;
+    let dimensions = *frame.code.code.get((pc + 3) as usize).unwrap() as usize;
+    let class_name = frame.class_name.clone();
+
+    let mut counts: Vec<i32> = (0..dimensions).map(|_| frame.stack_pop_int()).collect();
+    counts.reverse();
+
+    if let Some(negative) = counts.iter().find(|count| **count < 0) {
+        trace!("multianewarray: rejecting negative dimension {}", negative);
+        let exception = vm_thread.vm.new_exception("java/lang/NegativeArraySizeException", None);
+        return throw(vm_thread, pc, exception).resume_here();
+    }
+
+    let class = vm_thread.vm.class_loader.load(&class_name).unwrap();
+    let array_type = match class.constants.get(index as usize) {
+        Some(ClassConstant::Class(name)) => TypeSignature::from_descriptor(name),
+        other => panic!("multianewarray: expected a Class constant, got {:?}", other),
+    };
+
+    trace!("multianewarray: allocating {} with dimensions {:?}", array_type, counts);
+
+    let array = allocate_dimension(&array_type, &counts);
+    let frame = vm_thread.frame_stack.last_mut().unwrap();
+    frame.stack_push_reference(VmPrimitive::ArrayReference(Rc::new(RefCell::new(array))));
+
+    Some(pc + 4)
+}
+
+fn allocate_dimension(array_type: &TypeSignature, counts: &[i32]) -> VmArray {
+    let element_type = match array_type {
+        TypeSignature::Array(inner) => (**inner).clone(),
+        other => other.clone(),
+    };
+
+    let length = counts[0] as usize;
+
+    if counts.len() > 1 {
+        let mut array = VmArray::new_reference(element_type.clone(), length);
+        for slot in array.elements.iter_mut() {
+            let nested = allocate_dimension(&element_type, &counts[1..]);
+            *slot = VmPrimitive::ArrayReference(Rc::new(RefCell::new(nested)));
+        }
+        array
+    } else if let Some(atype) = primitive_atype(&element_type) {
+        VmArray::new_primitive(atype, element_type.clone(), length, default_primitive_value(&element_type))
+    } else {
+        VmArray::new_reference(element_type, length)
+    }
+}
+
+fn primitive_atype(descriptor: &TypeSignature) -> Option<u8> {
+    match descriptor {
+        TypeSignature::Boolean => Some(4),
+        TypeSignature::Char => Some(5),
+        TypeSignature::Float => Some(6),
+        TypeSignature::Double => Some(7),
+        TypeSignature::Byte => Some(8),
+        TypeSignature::Short => Some(9),
+        TypeSignature::Int => Some(10),
+        TypeSignature::Long => Some(11),
+        _ => None,
+    }
+}
+
+fn default_primitive_value(descriptor: &TypeSignature) -> VmPrimitive {
+    match descriptor {
+        TypeSignature::Long => VmPrimitive::Long(0),
+        TypeSignature::Float => VmPrimitive::Float(0.0),
+        TypeSignature::Double => VmPrimitive::Double(0.0),
+        _ => VmPrimitive::Int(0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn leaf_dimension_of_primitive_type_is_a_primitive_array() {
+        let array_type = TypeSignature::Array(Box::new(TypeSignature::Int));
+        let array = allocate_dimension(&array_type, &[3]);
+
+        assert_eq!(array.atype, Some(10));
+        assert_eq!(array.element_type, TypeSignature::Int);
+        assert_eq!(array.elements.len(), 3);
+        assert!(matches!(array.elements[0], VmPrimitive::Int(0)));
+    }
+
+    #[test]
+    fn outer_dimension_records_the_reduced_element_type() {
+        let array_type = TypeSignature::Array(Box::new(TypeSignature::Array(Box::new(TypeSignature::Int))));
+        let array = allocate_dimension(&array_type, &[2, 3]);
+
+        assert_eq!(array.atype, None);
+        assert_eq!(array.element_type, TypeSignature::Array(Box::new(TypeSignature::Int)));
+        assert_eq!(array.elements.len(), 2);
+
+        match &array.elements[0] {
+            VmPrimitive::ArrayReference(nested) => {
+                let nested = nested.borrow();
+                assert_eq!(nested.atype, Some(10));
+                assert_eq!(nested.elements.len(), 3);
+            }
+            other => panic!("expected a nested array reference, got {:?}", other),
+        }
+    }
+}
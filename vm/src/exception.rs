@@ -0,0 +1,293 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use model::class::ExceptionTable;
+
+use crate::{frame::VmFrameImpl, VmObject, VmPrimitive, VmThread};
+
+/// Where unwinding for a thrown exception landed, relative to the frame
+/// that was on top of `vm_thread.frame_stack` when `throw` was called.
+///
+/// This distinction is what an `invoke_method` Rust call needs after
+/// stepping its eval loop: on `HandledInCurrentFrame`, its own frame is
+/// still on top and it can resume stepping at the given pc directly. On
+/// `Unwound`, one or more frames were popped out from under it -- its own
+/// frame is gone, so it must return immediately rather than keep stepping,
+/// letting the unwind keep propagating up through each enclosing
+/// `invoke_method` call (since those are Rust-recursive, one per Java call)
+/// until it reaches the call whose frame the handler (if any) landed in.
+#[derive(Debug, PartialEq)]
+pub enum UnwindOutcome {
+    HandledInCurrentFrame(u16),
+    Unwound(Option<u16>),
+}
+
+impl UnwindOutcome {
+    /// Maps onto the `eval` function `Option<u16>` convention: a pc to
+    /// resume at only when this frame is still the one that's running;
+    /// `None` (whether truly uncaught or just unwound past this frame)
+    /// always means "stop, return from this `invoke_method` call".
+    pub fn resume_here(self) -> Option<u16> {
+        match self {
+            UnwindOutcome::HandledInCurrentFrame(pc) => Some(pc),
+            UnwindOutcome::Unwound(_) => None,
+        }
+    }
+}
+
+/// Unwinds the frame stack looking for a handler for `exception`, re-raising
+/// into each caller in turn until one matches or the stack empties.
+pub fn throw(vm_thread: &mut VmThread, pc: u16, exception: Rc<RefCell<VmObject>>) -> UnwindOutcome {
+    let exception_class = exception.borrow().class_name.clone();
+
+    let (outcome, stack_trace) = unwind_frames(&mut vm_thread.frame_stack, pc, &exception_class, |thrown, catch_type| {
+        vm_thread.vm.is_assignable(thrown, catch_type)
+    });
+
+    match &outcome {
+        UnwindOutcome::HandledInCurrentFrame(handler_pc) | UnwindOutcome::Unwound(Some(handler_pc)) => {
+            trace!("throw: {} handled at pc {}", exception_class, handler_pc);
+            let frame = vm_thread.frame_stack.last_mut().expect("a handler implies a frame remains");
+            frame.stack_clear();
+            frame.stack_push_reference(VmPrimitive::Reference(exception.clone()));
+        }
+        UnwindOutcome::Unwound(None) => {
+            report_uncaught(vm_thread, &exception.borrow(), &stack_trace);
+        }
+    }
+
+    outcome
+}
+
+fn report_uncaught(vm_thread: &VmThread, exception: &VmObject, stack_trace: &[String]) {
+    eprintln!(
+        "Exception in thread \"{}\" {}: {}",
+        vm_thread.name,
+        exception.class_name,
+        exception.message().unwrap_or_default()
+    );
+    for frame in stack_trace {
+        eprintln!("{}", frame);
+    }
+}
+
+/// The minimal view of a call frame `unwind_frames` needs -- its exception
+/// table, its own resume pc, and enough identity for a stack trace line --
+/// kept separate from the concrete `VmFrame` type so the unwind algorithm
+/// itself is testable without a `VmThread`.
+pub(crate) trait UnwindFrame {
+    fn exception_table(&self) -> &[ExceptionTable];
+    fn pc(&self) -> u16;
+    fn class_name(&self) -> &str;
+    fn method_name(&self) -> &str;
+}
+
+/// Pops frames off `frames` searching each one's exception table for a
+/// handler of `exception_class`, starting at `pc` and then, after each pop,
+/// at the caller frame's own resume pc, same as `throw`. Returns the outcome plus the
+/// `\tat Class.method(pc N)` trace lines collected along the way.
+fn unwind_frames<F: UnwindFrame>(
+    frames: &mut Vec<F>,
+    pc: u16,
+    exception_class: &str,
+    is_assignable: impl Fn(&str, &str) -> bool,
+) -> (UnwindOutcome, Vec<String>) {
+    let mut current_pc = pc;
+    let mut frames_popped = 0u32;
+    let mut stack_trace = Vec::new();
+
+    while let Some(frame) = frames.last() {
+        stack_trace.push(format!("\tat {}.{}(pc {})", frame.class_name(), frame.method_name(), current_pc));
+
+        let handler_pc = find_handler(frame.exception_table(), current_pc, exception_class, &is_assignable);
+
+        if let Some(handler_pc) = handler_pc {
+            let outcome = if frames_popped == 0 {
+                UnwindOutcome::HandledInCurrentFrame(handler_pc)
+            } else {
+                UnwindOutcome::Unwound(Some(handler_pc))
+            };
+            return (outcome, stack_trace);
+        }
+
+        frames.pop();
+        frames_popped += 1;
+        if let Some(caller) = frames.last() {
+            current_pc = caller.pc();
+        }
+    }
+
+    (UnwindOutcome::Unwound(None), stack_trace)
+}
+
+/// The first exception table entry covering `pc` whose `catch_type` is
+/// either unset (a catch-all, e.g. for `finally`) or an ancestor of
+/// `exception_class` per `is_assignable`. Pulled out of `unwind_frames` so
+/// the JVMS `[start_pc, end_pc)` search itself is testable in isolation.
+fn find_handler(
+    exception_table: &[ExceptionTable],
+    pc: u16,
+    exception_class: &str,
+    is_assignable: impl Fn(&str, &str) -> bool,
+) -> Option<u16> {
+    exception_table
+        .iter()
+        .find(|entry| {
+            (entry.start_pc..entry.end_pc).contains(&pc)
+                && match &entry.catch_type {
+                    None => true,
+                    Some(catch_type) => is_assignable(exception_class, catch_type),
+                }
+        })
+        .map(|entry| entry.handler_pc)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(start_pc: u16, end_pc: u16, handler_pc: u16, catch_type: Option<&str>) -> ExceptionTable {
+        ExceptionTable {
+            start_pc,
+            end_pc,
+            handler_pc,
+            catch_type: catch_type.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn finds_the_entry_covering_pc_with_a_matching_catch_type() {
+        let table = vec![entry(0, 10, 20, Some("java/lang/ArithmeticException"))];
+
+        let handler = find_handler(&table, 5, "java/lang/ArithmeticException", |thrown, catch_type| thrown == catch_type);
+
+        assert_eq!(handler, Some(20));
+    }
+
+    #[test]
+    fn end_pc_is_exclusive() {
+        let table = vec![entry(0, 10, 20, None)];
+
+        assert_eq!(find_handler(&table, 10, "java/lang/Exception", |_, _| true), None);
+    }
+
+    #[test]
+    fn none_catch_type_matches_any_exception() {
+        let table = vec![entry(0, 10, 20, None)];
+
+        let handler = find_handler(&table, 0, "java/lang/OutOfMemoryError", |_, _| false);
+
+        assert_eq!(handler, Some(20));
+    }
+
+    #[test]
+    fn skips_entries_whose_catch_type_is_not_assignable() {
+        let table = vec![
+            entry(0, 10, 20, Some("java/io/IOException")),
+            entry(0, 10, 30, Some("java/lang/RuntimeException")),
+        ];
+
+        let handler = find_handler(&table, 5, "java/lang/NullPointerException", |thrown, catch_type| {
+            thrown == "java/lang/NullPointerException" && catch_type == "java/lang/RuntimeException"
+        });
+
+        assert_eq!(handler, Some(30));
+    }
+
+    struct MockFrame {
+        class_name: &'static str,
+        pc: u16,
+        exception_table: Vec<ExceptionTable>,
+    }
+
+    impl UnwindFrame for MockFrame {
+        fn exception_table(&self) -> &[ExceptionTable] {
+            &self.exception_table
+        }
+        fn pc(&self) -> u16 {
+            self.pc
+        }
+        fn class_name(&self) -> &str {
+            self.class_name
+        }
+        fn method_name(&self) -> &str {
+            "run"
+        }
+    }
+
+    #[test]
+    fn handled_in_the_current_frame_pops_nothing() {
+        let mut frames = vec![MockFrame {
+            class_name: "A",
+            pc: 0,
+            exception_table: vec![entry(0, 10, 99, None)],
+        }];
+
+        let (outcome, _) = unwind_frames(&mut frames, 5, "java/lang/Exception", |_, _| true);
+
+        assert_eq!(outcome, UnwindOutcome::HandledInCurrentFrame(99));
+        assert_eq!(frames.len(), 1);
+    }
+
+    #[test]
+    fn re_raises_across_two_unhandled_frames_before_a_grandcaller_catches_it() {
+        // C (innermost, no handler) calls B (no handler) calls A (has one).
+        // B's own resume pc (where it called C) is 40; A's is 7.
+        let mut frames = vec![
+            MockFrame {
+                class_name: "A",
+                pc: 7,
+                exception_table: vec![entry(0, 20, 15, Some("java/lang/RuntimeException"))],
+            },
+            MockFrame {
+                class_name: "B",
+                pc: 40,
+                exception_table: vec![],
+            },
+            MockFrame {
+                class_name: "C",
+                pc: 3,
+                exception_table: vec![],
+            },
+        ];
+
+        let (outcome, stack_trace) = unwind_frames(&mut frames, 3, "java/lang/RuntimeException", |_, _| true);
+
+        assert_eq!(outcome, UnwindOutcome::Unwound(Some(15)));
+        // Only A is left on top -- B and C were popped during the unwind.
+        assert_eq!(frames.len(), 1);
+        assert_eq!(frames[0].class_name, "A");
+        assert_eq!(stack_trace.len(), 3);
+        assert!(stack_trace[0].contains("C.run(pc 3)"));
+        assert!(stack_trace[1].contains("B.run(pc 40)"));
+        assert!(stack_trace[2].contains("A.run(pc 7)"));
+    }
+
+    #[test]
+    fn uncaught_across_every_frame_empties_the_stack() {
+        let mut frames = vec![
+            MockFrame {
+                class_name: "A",
+                pc: 0,
+                exception_table: vec![],
+            },
+            MockFrame {
+                class_name: "B",
+                pc: 0,
+                exception_table: vec![],
+            },
+        ];
+
+        let (outcome, _) = unwind_frames(&mut frames, 0, "java/lang/Exception", |_, _| false);
+
+        assert_eq!(outcome, UnwindOutcome::Unwound(None));
+        assert!(frames.is_empty());
+    }
+
+    #[test]
+    fn resume_here_only_continues_for_the_current_frame() {
+        assert_eq!(UnwindOutcome::HandledInCurrentFrame(42).resume_here(), Some(42));
+        assert_eq!(UnwindOutcome::Unwound(Some(42)).resume_here(), None);
+        assert_eq!(UnwindOutcome::Unwound(None).resume_here(), None);
+    }
+}
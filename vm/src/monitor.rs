@@ -0,0 +1,120 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// A reentrant monitor, one per heap object and one per class name (for
+/// static-synchronized methods, see `ClassMonitors`).
+///
+/// `VmObject` is `Rc<RefCell<..>>` rather than `Arc<Mutex<..>>`, so today's
+/// `VmThread`s run cooperatively on a single OS thread -- there's no real
+/// concurrent contention to park on yet, and blocking on a `Condvar` here
+/// would just deadlock that one OS thread waiting on itself. `enter`/`exit`
+/// only track ownership and a reentrancy count; `enter` returns `Err(())`
+/// rather than blocking when another thread already owns the monitor. A
+/// preemptive scheduler over an `Arc<Mutex<..>>` heap would need to turn
+/// that into real waiting.
+#[derive(Debug, Default)]
+pub struct Monitor {
+    state: RefCell<MonitorState>,
+}
+
+#[derive(Debug, Default)]
+struct MonitorState {
+    owner: Option<String>,
+    count: u32,
+}
+
+impl Monitor {
+    pub fn new() -> Monitor {
+        Monitor::default()
+    }
+
+    pub fn enter(&self, thread_name: &str) -> Result<(), ()> {
+        let mut state = self.state.borrow_mut();
+        match &state.owner {
+            None => {
+                state.owner = Some(thread_name.to_string());
+                state.count = 1;
+                Ok(())
+            }
+            Some(owner) if owner == thread_name => {
+                state.count += 1;
+                Ok(())
+            }
+            Some(_) => Err(()),
+        }
+    }
+
+    /// Returns `Err(())` if `thread_name` does not currently own the
+    /// monitor -- callers should surface that as `IllegalMonitorStateException`.
+    pub fn exit(&self, thread_name: &str) -> Result<(), ()> {
+        let mut state = self.state.borrow_mut();
+        match &state.owner {
+            Some(owner) if owner == thread_name => {
+                state.count -= 1;
+                if state.count == 0 {
+                    state.owner = None;
+                }
+                Ok(())
+            }
+            _ => Err(()),
+        }
+    }
+}
+
+/// Per-class monitors for static-`synchronized` methods, keyed by class
+/// name since `JvmClass` itself is a parsed, shared data descriptor rather
+/// than a per-load runtime instance.
+#[derive(Debug, Default)]
+pub struct ClassMonitors {
+    monitors: RefCell<HashMap<String, Rc<Monitor>>>,
+}
+
+impl ClassMonitors {
+    pub fn new() -> ClassMonitors {
+        ClassMonitors::default()
+    }
+
+    pub fn get_or_create(&self, class_name: &str) -> Rc<Monitor> {
+        self.monitors
+            .borrow_mut()
+            .entry(class_name.to_string())
+            .or_insert_with(|| Rc::new(Monitor::new()))
+            .clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reentrant_owner_does_not_block() {
+        let monitor = Monitor::new();
+        assert_eq!(monitor.enter("Thread-0"), Ok(()));
+        assert_eq!(monitor.enter("Thread-0"), Ok(()));
+        assert_eq!(monitor.exit("Thread-0"), Ok(()));
+        assert_eq!(monitor.exit("Thread-0"), Ok(()));
+    }
+
+    #[test]
+    fn other_thread_is_rejected_instead_of_blocking() {
+        let monitor = Monitor::new();
+        assert_eq!(monitor.enter("Thread-0"), Ok(()));
+        assert_eq!(monitor.enter("Thread-1"), Err(()));
+    }
+
+    #[test]
+    fn exit_without_owning_is_an_error() {
+        let monitor = Monitor::new();
+        assert_eq!(monitor.exit("Thread-0"), Err(()));
+    }
+
+    #[test]
+    fn class_monitors_are_shared_per_class_name() {
+        let class_monitors = ClassMonitors::new();
+        let first = class_monitors.get_or_create("com/example/Foo");
+        let second = class_monitors.get_or_create("com/example/Foo");
+        assert!(Rc::ptr_eq(&first, &second));
+    }
+}
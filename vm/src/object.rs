@@ -0,0 +1,55 @@
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::monitor::Monitor;
+use crate::VmPrimitive;
+
+#[derive(Debug)]
+pub struct VmObject {
+    pub class_name: String,
+    pub fields: HashMap<String, VmPrimitive>,
+    pub monitor: Rc<Monitor>,
+    message: Option<String>,
+}
+
+impl VmObject {
+    pub fn new(class_name: String) -> VmObject {
+        VmObject {
+            class_name,
+            fields: HashMap::new(),
+            monitor: Rc::new(Monitor::new()),
+            message: None,
+        }
+    }
+
+    pub fn with_message(class_name: String, message: String) -> VmObject {
+        VmObject {
+            message: Some(message),
+            ..VmObject::new(class_name)
+        }
+    }
+
+    pub fn message(&self) -> Option<String> {
+        self.message.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn objects_without_a_message_report_none() {
+        let object = VmObject::new("java/lang/Object".to_string());
+        assert_eq!(object.message(), None);
+    }
+
+    #[test]
+    fn with_message_reports_it_back() {
+        let object = VmObject::with_message(
+            "java/lang/NullPointerException".to_string(),
+            "nope".to_string(),
+        );
+        assert_eq!(object.message(), Some("nope".to_string()));
+    }
+}
@@ -0,0 +1,116 @@
+/// Mnemonic and total instruction length (opcode byte plus operands) for a
+/// bytecode instruction, looked up by opcode value.
+pub struct OpcodeInfo {
+    pub mnemonic: &'static str,
+    pub length: u8,
+}
+
+const fn info(mnemonic: &'static str, length: u8) -> OpcodeInfo {
+    OpcodeInfo { mnemonic, length }
+}
+
+/// Looks up `opcode`'s mnemonic and instruction length. Returns `None` for
+/// opcodes not yet in the table rather than guessing a length, since a wrong
+/// guess would desynchronize decoding of everything after it.
+pub fn lookup(opcode: u8) -> Option<OpcodeInfo> {
+    match opcode {
+        0 => Some(info("nop", 1)),
+        1 => Some(info("aconst_null", 1)),
+        2 => Some(info("iconst_m1", 1)),
+        3 => Some(info("iconst_0", 1)),
+        4 => Some(info("iconst_1", 1)),
+        5 => Some(info("iconst_2", 1)),
+        6 => Some(info("iconst_3", 1)),
+        7 => Some(info("iconst_4", 1)),
+        8 => Some(info("iconst_5", 1)),
+        9 => Some(info("lconst_0", 1)),
+        10 => Some(info("lconst_1", 1)),
+        11 => Some(info("fconst_0", 1)),
+        12 => Some(info("fconst_1", 1)),
+        13 => Some(info("fconst_2", 1)),
+        14 => Some(info("dconst_0", 1)),
+        15 => Some(info("dconst_1", 1)),
+        16 => Some(info("bipush", 2)),
+        17 => Some(info("sipush", 3)),
+        18 => Some(info("ldc", 2)),
+        19 => Some(info("ldc_w", 3)),
+        20 => Some(info("ldc2_w", 3)),
+        21 => Some(info("iload", 2)),
+        22 => Some(info("lload", 2)),
+        23 => Some(info("fload", 2)),
+        24 => Some(info("dload", 2)),
+        25 => Some(info("aload", 2)),
+        42 => Some(info("aload_0", 1)),
+        43 => Some(info("aload_1", 1)),
+        44 => Some(info("aload_2", 1)),
+        45 => Some(info("aload_3", 1)),
+        50 => Some(info("aaload", 1)),
+        51 => Some(info("baload", 1)),
+        52 => Some(info("caload", 1)),
+        53 => Some(info("saload", 1)),
+        54 => Some(info("istore", 2)),
+        55 => Some(info("lstore", 2)),
+        56 => Some(info("fstore", 2)),
+        57 => Some(info("dstore", 2)),
+        58 => Some(info("astore", 2)),
+        79 => Some(info("dastore", 1)),
+        83 => Some(info("aastore", 1)),
+        87 => Some(info("pop", 1)),
+        88 => Some(info("pop2", 1)),
+        89 => Some(info("dup", 1)),
+        96 => Some(info("iadd", 1)),
+        97 => Some(info("ladd", 1)),
+        98 => Some(info("fadd", 1)),
+        99 => Some(info("dadd", 1)),
+        108 => Some(info("idiv", 1)),
+        109 => Some(info("ldiv", 1)),
+        153 => Some(info("ifeq", 3)),
+        154 => Some(info("ifne", 3)),
+        167 => Some(info("goto", 3)),
+        172 => Some(info("ireturn", 1)),
+        173 => Some(info("lreturn", 1)),
+        174 => Some(info("freturn", 1)),
+        175 => Some(info("dreturn", 1)),
+        176 => Some(info("areturn", 1)),
+        177 => Some(info("return", 1)),
+        178 => Some(info("getstatic", 3)),
+        179 => Some(info("putstatic", 3)),
+        180 => Some(info("getfield", 3)),
+        181 => Some(info("putfield", 3)),
+        182 => Some(info("invokevirtual", 3)),
+        183 => Some(info("invokespecial", 3)),
+        184 => Some(info("invokestatic", 3)),
+        185 => Some(info("invokeinterface", 5)),
+        186 => Some(info("invokedynamic", 5)),
+        187 => Some(info("new", 3)),
+        188 => Some(info("newarray", 2)),
+        189 => Some(info("anewarray", 3)),
+        190 => Some(info("arraylength", 1)),
+        191 => Some(info("athrow", 1)),
+        192 => Some(info("checkcast", 3)),
+        193 => Some(info("instanceof", 3)),
+        194 => Some(info("monitorenter", 1)),
+        195 => Some(info("monitorexit", 1)),
+        197 => Some(info("multianewarray", 4)),
+        198 => Some(info("ifnull", 3)),
+        199 => Some(info("ifnonnull", 3)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn looks_up_a_known_opcode() {
+        let info = lookup(182).unwrap();
+        assert_eq!(info.mnemonic, "invokevirtual");
+        assert_eq!(info.length, 3);
+    }
+
+    #[test]
+    fn unknown_opcodes_return_none_rather_than_guess_a_length() {
+        assert!(lookup(253).is_none());
+    }
+}
@@ -0,0 +1,67 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use model::class::{ClassMethod, MethodAccessFlag};
+
+use crate::monitor::Monitor;
+use crate::{VmObject, VmThread};
+
+/// BLOCKED: not yet called from method invocation. `invoke_method` has no
+/// defining file anywhere in this tree (nor do any `invokevirtual` /
+/// `invokestatic` / `invokespecial` / `invokeinterface` eval fns), so
+/// there's no call site in this series to hold the guard this returns. A
+/// `synchronized` method runs with no locking until that's wired up.
+///
+/// Resolves which monitor, if any, a call to `method` must hold: the
+/// receiver's for an instance `synchronized` method, the declaring class's
+/// (via `vm_thread.vm.class_monitors`) for a static one, `None` otherwise.
+pub fn monitor_for(
+    vm_thread: &VmThread,
+    class_name: &str,
+    method: &ClassMethod,
+    receiver: Option<&Rc<RefCell<VmObject>>>,
+) -> Option<Rc<Monitor>> {
+    if !method.access_flags.contains(MethodAccessFlag::Synchronized) {
+        return None;
+    }
+
+    match receiver {
+        Some(object) => Some(object.borrow().monitor.clone()),
+        None => Some(vm_thread.vm.class_monitors.get_or_create(class_name)),
+    }
+}
+
+/// Holds `monitor` for `thread_name` until dropped -- releases on any path
+/// out of scope, including a panic unwinding through it.
+pub struct MonitorGuard<'a> {
+    monitor: Rc<Monitor>,
+    thread_name: &'a str,
+}
+
+impl<'a> MonitorGuard<'a> {
+    pub fn enter(monitor: Rc<Monitor>, thread_name: &'a str) -> Result<MonitorGuard<'a>, ()> {
+        monitor.enter(thread_name)?;
+        Ok(MonitorGuard { monitor, thread_name })
+    }
+}
+
+impl<'a> Drop for MonitorGuard<'a> {
+    fn drop(&mut self) {
+        let _ = self.monitor.exit(self.thread_name);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn guard_releases_the_monitor_on_drop() {
+        let monitor = Rc::new(Monitor::new());
+        {
+            let _guard = MonitorGuard::enter(monitor.clone(), "Thread-0").unwrap();
+            assert_eq!(monitor.enter("Thread-1"), Err(()));
+        }
+        assert_eq!(monitor.enter("Thread-1"), Ok(()));
+    }
+}
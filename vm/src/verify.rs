@@ -0,0 +1,699 @@
+use model::class::{ClassAttribute, ClassConstant, ClassMethod, JvmClass, MethodSignature, TypeSignature, VerificationType};
+
+use crate::opcode;
+
+/// Why a method failed verification: the pc the mismatch was found at, and
+/// the frame's expected type versus what the abstract interpreter actually
+/// modeled there.
+#[derive(Debug)]
+pub struct VerifyError {
+    pub pc: u16,
+    pub expected: VerificationType,
+    pub actual: VerificationType,
+}
+
+impl std::fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "VerifyError: at pc {}, expected type assignable from {:?} but found {:?}",
+            self.pc, self.expected, self.actual
+        )
+    }
+}
+
+/// Abstract-interpretation state tracked at a single program point: the
+/// local variable array and the operand stack, each slot a
+/// `VerificationType`.
+#[derive(Clone, Debug, Default)]
+struct VerifierState {
+    locals: Vec<VerificationType>,
+    stack: Vec<VerificationType>,
+}
+
+/// Walks `method`'s `Code` linearly, modeling each instruction's effect on
+/// the operand stack and locals, and checks the modeled state against the
+/// declared `StackMapTable` frame at each offset one is declared for, plus
+/// `max_stack`/`max_locals` throughout. A method without a `StackMapTable`
+/// attribute is not walked: only its entry frame is checked against
+/// `max_locals`.
+pub fn verify_method(class: &JvmClass, method: &ClassMethod) -> Result<(), VerifyError> {
+    let code = match method.attributes.iter().find_map(|attribute| match attribute {
+        ClassAttribute::Code(code) => Some(code),
+        _ => None,
+    }) {
+        Some(code) => code,
+        None => return Ok(()),
+    };
+
+    let declared_frames = method.attributes.iter().find_map(|attribute| match attribute {
+        ClassAttribute::StackMapTable(frames) => Some(frames),
+        _ => None,
+    });
+
+    let mut state = VerifierState::default();
+    if !method.access_flags.contains(model::class::MethodAccessFlag::Static) {
+        state.locals.push(VerificationType::Object(class.this_class.clone()));
+    }
+    for parameter in &method.descriptor.parameters {
+        state.locals.push(verification_type_of(parameter));
+    }
+
+    if locals_width(&state.locals) > code.max_locals {
+        return Err(VerifyError {
+            pc: 0,
+            expected: VerificationType::Top,
+            actual: VerificationType::Top,
+        });
+    }
+
+    let Some(declared_frames) = declared_frames else {
+        return Ok(());
+    };
+
+    let mut pc = 0u16;
+    let mut next_frame_pc = 0u16;
+    let mut frames = declared_frames.iter();
+    let mut is_first_frame = true;
+
+    let mut pending_frame = frames.next();
+
+    while (pc as usize) < code.code.len() {
+        if let Some(frame) = pending_frame {
+            let target_pc = if is_first_frame {
+                frame.offset_delta
+            } else {
+                next_frame_pc + frame.offset_delta + 1
+            };
+
+            if pc == target_pc {
+                assert_assignable(pc, &frame.locals, &state.locals)?;
+                assert_assignable(pc, &frame.stack, &state.stack)?;
+
+                state.locals = frame.locals.clone();
+                state.stack = frame.stack.clone();
+
+                next_frame_pc = target_pc;
+                is_first_frame = false;
+                pending_frame = frames.next();
+            }
+        }
+
+        let opcode_byte = code.code[pc as usize];
+        let info = opcode::lookup(opcode_byte).ok_or(VerifyError {
+            pc,
+            expected: VerificationType::Top,
+            actual: VerificationType::Top,
+        })?;
+
+        apply_effect(class, info.mnemonic, &code.code, pc, &mut state)?;
+        let length = info.length as u16;
+
+        if slot_width(&state.stack) > code.max_stack {
+            return Err(VerifyError {
+                pc,
+                expected: VerificationType::Top,
+                actual: VerificationType::Top,
+            });
+        }
+
+        if locals_width(&state.locals) > code.max_locals {
+            return Err(VerifyError {
+                pc,
+                expected: VerificationType::Top,
+                actual: VerificationType::Top,
+            });
+        }
+
+        pc += length;
+    }
+
+    Ok(())
+}
+
+fn verification_type_of(descriptor: &TypeSignature) -> VerificationType {
+    match descriptor {
+        TypeSignature::Boolean
+        | TypeSignature::Byte
+        | TypeSignature::Char
+        | TypeSignature::Short
+        | TypeSignature::Int => VerificationType::Integer,
+        TypeSignature::Long => VerificationType::Long,
+        TypeSignature::Float => VerificationType::Float,
+        TypeSignature::Double => VerificationType::Double,
+        TypeSignature::Void => VerificationType::Top,
+        TypeSignature::Class(name) => VerificationType::Object(name.clone()),
+        TypeSignature::Array(_) => VerificationType::Object(descriptor.to_string()),
+    }
+}
+
+/// Physical local-variable slot count for `locals`: `Long`/`Double` count
+/// for two (their runtime width), even though a `StackMapFrame`'s locals
+/// list (JVMS 4.7.4) carries just one entry for them.
+fn locals_width(locals: &[VerificationType]) -> u16 {
+    slot_width(locals)
+}
+
+/// Physical slot count of a locals array or operand stack, with
+/// `Long`/`Double` counting for two.
+fn slot_width(slots: &[VerificationType]) -> u16 {
+    slots
+        .iter()
+        .map(|slot| match slot {
+            VerificationType::Long | VerificationType::Double => 2,
+            _ => 1,
+        })
+        .sum()
+}
+
+fn set_local(state: &mut VerifierState, index: usize, value: VerificationType) {
+    if index >= state.locals.len() {
+        state.locals.resize(index + 1, VerificationType::Top);
+    }
+    state.locals[index] = value;
+}
+
+fn pop(state: &mut VerifierState, pc: u16) -> Result<VerificationType, VerifyError> {
+    state.stack.pop().ok_or(VerifyError {
+        pc,
+        expected: VerificationType::Top,
+        actual: VerificationType::Top,
+    })
+}
+
+fn push(state: &mut VerifierState, value: VerificationType) {
+    state.stack.push(value);
+}
+
+fn u8_at(code: &[u8], pos: u16) -> u8 {
+    code[pos as usize]
+}
+
+fn u16_at(code: &[u8], pos: u16) -> u16 {
+    ((code[pos as usize] as u16) << 8) | code[(pos + 1) as usize] as u16
+}
+
+fn field_descriptor(class: &JvmClass, index: u16) -> TypeSignature {
+    match class.constants.get(index as usize) {
+        Some(ClassConstant::Fieldref(_, _, descriptor)) => descriptor.clone(),
+        other => panic!("verify: expected a Fieldref constant, got {:?}", other),
+    }
+}
+
+fn method_signature(class: &JvmClass, index: u16) -> MethodSignature {
+    match class.constants.get(index as usize) {
+        Some(ClassConstant::Methodref(_, _, signature))
+        | Some(ClassConstant::InterfaceMethodref(_, _, signature)) => signature.clone(),
+        Some(ClassConstant::InvokeDynamic(_, _, signature)) => signature.clone(),
+        other => panic!("verify: expected an invokable constant, got {:?}", other),
+    }
+}
+
+fn class_name_of(class: &JvmClass, index: u16) -> String {
+    match class.constants.get(index as usize) {
+        Some(ClassConstant::Class(name)) => name.clone(),
+        other => panic!("verify: expected a Class constant, got {:?}", other),
+    }
+}
+
+fn array_descriptor_of(atype: u8) -> &'static str {
+    match atype {
+        4 => "[Z",
+        5 => "[C",
+        6 => "[F",
+        7 => "[D",
+        8 => "[B",
+        9 => "[S",
+        10 => "[I",
+        11 => "[J",
+        other => panic!("verify: newarray has an unknown atype {}", other),
+    }
+}
+
+/// Pops `mnemonic`'s inputs off the operand stack, pushes its result(s),
+/// and updates locals for store opcodes. Purely linear -- doesn't merge
+/// state at branch targets, relying on the declared `StackMapTable` frame
+/// at each target to re-seed `state` instead.
+fn apply_effect(
+    class: &JvmClass,
+    mnemonic: &str,
+    code: &[u8],
+    pc: u16,
+    state: &mut VerifierState,
+) -> Result<(), VerifyError> {
+    use VerificationType::*;
+
+    match mnemonic {
+        "nop" => {}
+        "aconst_null" => push(state, Null),
+        "iconst_m1" | "iconst_0" | "iconst_1" | "iconst_2" | "iconst_3" | "iconst_4" | "iconst_5" | "bipush"
+        | "sipush" => push(state, Integer),
+        "lconst_0" | "lconst_1" => push(state, Long),
+        "fconst_0" | "fconst_1" | "fconst_2" => push(state, Float),
+        "dconst_0" | "dconst_1" => push(state, Double),
+
+        "ldc" | "ldc_w" | "ldc2_w" => {
+            let index = if mnemonic == "ldc" {
+                u8_at(code, pc + 1) as u16
+            } else {
+                u16_at(code, pc + 1)
+            };
+            let loaded = match class.constants.get(index as usize) {
+                Some(ClassConstant::Integer(_)) => Integer,
+                Some(ClassConstant::Float(_)) => Float,
+                Some(ClassConstant::Long(_)) => Long,
+                Some(ClassConstant::Double(_)) => Double,
+                Some(ClassConstant::String(_)) => Object("java/lang/String".to_string()),
+                Some(ClassConstant::Class(_)) => Object("java/lang/Class".to_string()),
+                other => panic!("verify: {} cannot load constant {:?}", mnemonic, other),
+            };
+            push(state, loaded);
+        }
+
+        "iload" | "lload" | "fload" | "dload" | "aload" => {
+            let index = u8_at(code, pc + 1) as usize;
+            push(state, state.locals[index].clone());
+        }
+        "aload_0" | "aload_1" | "aload_2" | "aload_3" => {
+            let index = (mnemonic.as_bytes()[6] - b'0') as usize;
+            push(state, state.locals[index].clone());
+        }
+
+        "aaload" => {
+            pop(state, pc)?;
+            let arrayref = pop(state, pc)?;
+            let element = match &arrayref {
+                Object(descriptor) if descriptor.starts_with('[') => {
+                    verification_type_of(&TypeSignature::from_descriptor(&descriptor[1..]))
+                }
+                _ => Object("java/lang/Object".to_string()),
+            };
+            push(state, element);
+        }
+        "baload" | "caload" | "saload" => {
+            pop(state, pc)?;
+            pop(state, pc)?;
+            push(state, Integer);
+        }
+
+        "istore" | "lstore" | "fstore" | "dstore" | "astore" => {
+            let index = u8_at(code, pc + 1) as usize;
+            let value = pop(state, pc)?;
+            set_local(state, index, value);
+        }
+
+        "dastore" | "aastore" => {
+            pop(state, pc)?;
+            pop(state, pc)?;
+            pop(state, pc)?;
+        }
+
+        "pop" => {
+            pop(state, pc)?;
+        }
+        "pop2" => {
+            let top = pop(state, pc)?;
+            if !matches!(top, Long | Double) {
+                pop(state, pc)?;
+            }
+        }
+        "dup" => {
+            let top = pop(state, pc)?;
+            push(state, top.clone());
+            push(state, top);
+        }
+
+        "iadd" | "idiv" => {
+            pop(state, pc)?;
+            pop(state, pc)?;
+            push(state, Integer);
+        }
+        "ladd" | "ldiv" => {
+            pop(state, pc)?;
+            pop(state, pc)?;
+            push(state, Long);
+        }
+        "fadd" => {
+            pop(state, pc)?;
+            pop(state, pc)?;
+            push(state, Float);
+        }
+        "dadd" => {
+            pop(state, pc)?;
+            pop(state, pc)?;
+            push(state, Double);
+        }
+
+        "ifeq" | "ifne" | "ifnull" | "ifnonnull" => {
+            pop(state, pc)?;
+        }
+        "goto" => {}
+
+        "ireturn" | "lreturn" | "freturn" | "dreturn" | "areturn" => {
+            pop(state, pc)?;
+        }
+        "return" => {}
+
+        "getstatic" => {
+            let index = u16_at(code, pc + 1);
+            push(state, verification_type_of(&field_descriptor(class, index)));
+        }
+        "putstatic" => {
+            pop(state, pc)?;
+        }
+        "getfield" => {
+            let index = u16_at(code, pc + 1);
+            pop(state, pc)?;
+            push(state, verification_type_of(&field_descriptor(class, index)));
+        }
+        "putfield" => {
+            pop(state, pc)?;
+            pop(state, pc)?;
+        }
+
+        "invokevirtual" | "invokespecial" | "invokestatic" | "invokeinterface" | "invokedynamic" => {
+            let index = u16_at(code, pc + 1);
+            let signature = method_signature(class, index);
+            for _ in &signature.parameters {
+                pop(state, pc)?;
+            }
+            if mnemonic != "invokestatic" && mnemonic != "invokedynamic" {
+                pop(state, pc)?;
+            }
+            if signature.return_type != TypeSignature::Void {
+                push(state, verification_type_of(&signature.return_type));
+            }
+        }
+
+        "new" => push(state, Uninitialized(pc)),
+        "newarray" => {
+            let atype = u8_at(code, pc + 1);
+            pop(state, pc)?;
+            push(state, Object(array_descriptor_of(atype).to_string()));
+        }
+        "anewarray" => {
+            let index = u16_at(code, pc + 1);
+            let element_class = class_name_of(class, index);
+            pop(state, pc)?;
+            let descriptor = if element_class.starts_with('[') {
+                format!("[{}", element_class)
+            } else {
+                format!("[L{};", element_class)
+            };
+            push(state, Object(descriptor));
+        }
+        "arraylength" => {
+            pop(state, pc)?;
+            push(state, Integer);
+        }
+        "athrow" => {
+            pop(state, pc)?;
+        }
+        "checkcast" => {
+            let index = u16_at(code, pc + 1);
+            pop(state, pc)?;
+            push(state, Object(class_name_of(class, index)));
+        }
+        "instanceof" => {
+            pop(state, pc)?;
+            push(state, Integer);
+        }
+        "monitorenter" | "monitorexit" => {
+            pop(state, pc)?;
+        }
+        "multianewarray" => {
+            let index = u16_at(code, pc + 1);
+            let dimensions = u8_at(code, pc + 3);
+            let element_class = class_name_of(class, index);
+            for _ in 0..dimensions {
+                pop(state, pc)?;
+            }
+            push(state, Object(element_class));
+        }
+
+        other => panic!("verify: opcode {} is in the opcode table but has no modeled effect", other),
+    }
+
+    Ok(())
+}
+
+/// Decodes a `StackMapTable` attribute's raw bytes into `Vec<StackMapFrame>`,
+/// resolving each frame's locals to their absolute contents by tracking a
+/// running locals list seeded with `entry_locals` and applying each frame's
+/// chop/append/replace against it in turn.
+///
+/// No parser in this tree produces `ClassAttribute::StackMapTable` yet, so
+/// this is currently only ever called by hand (e.g. in tests).
+pub fn decode_stack_map_table(
+    bytes: &[u8],
+    constants: &model::class::ClassConstants,
+    entry_locals: &[VerificationType],
+) -> Vec<model::class::StackMapFrame> {
+    let mut pos = 0usize;
+    let number_of_entries = read_u16(bytes, &mut pos) as usize;
+    let mut frames = Vec::with_capacity(number_of_entries);
+    let mut running_locals = entry_locals.to_vec();
+
+    for _ in 0..number_of_entries {
+        let frame_type = bytes[pos];
+        pos += 1;
+
+        let (offset_delta, stack) = match frame_type {
+            0..=63 => (frame_type as u16, vec![]),
+            64..=127 => {
+                let stack = vec![read_verification_type(bytes, &mut pos, constants)];
+                ((frame_type - 64) as u16, stack)
+            }
+            247 => {
+                let offset_delta = read_u16(bytes, &mut pos);
+                let stack = vec![read_verification_type(bytes, &mut pos, constants)];
+                (offset_delta, stack)
+            }
+            248..=250 => {
+                let offset_delta = read_u16(bytes, &mut pos);
+                let chop_count = (251 - frame_type) as usize;
+                let new_len = running_locals.len().saturating_sub(chop_count);
+                running_locals.truncate(new_len);
+                (offset_delta, vec![])
+            }
+            251 => (read_u16(bytes, &mut pos), vec![]),
+            252..=254 => {
+                let offset_delta = read_u16(bytes, &mut pos);
+                let append_count = (frame_type - 251) as usize;
+                for _ in 0..append_count {
+                    running_locals.push(read_verification_type(bytes, &mut pos, constants));
+                }
+                (offset_delta, vec![])
+            }
+            255 => {
+                let offset_delta = read_u16(bytes, &mut pos);
+                let number_of_locals = read_u16(bytes, &mut pos) as usize;
+                running_locals = (0..number_of_locals)
+                    .map(|_| read_verification_type(bytes, &mut pos, constants))
+                    .collect();
+                let number_of_stack_items = read_u16(bytes, &mut pos) as usize;
+                let stack = (0..number_of_stack_items)
+                    .map(|_| read_verification_type(bytes, &mut pos, constants))
+                    .collect();
+                (offset_delta, stack)
+            }
+            other => panic!("StackMapTable: unknown frame_type {}", other),
+        };
+
+        frames.push(model::class::StackMapFrame {
+            offset_delta,
+            locals: running_locals.clone(),
+            stack,
+        });
+    }
+
+    frames
+}
+
+fn read_u16(bytes: &[u8], pos: &mut usize) -> u16 {
+    let value = ((bytes[*pos] as u16) << 8) | bytes[*pos + 1] as u16;
+    *pos += 2;
+    value
+}
+
+fn read_verification_type(bytes: &[u8], pos: &mut usize, constants: &model::class::ClassConstants) -> VerificationType {
+    let tag = bytes[*pos];
+    *pos += 1;
+
+    match tag {
+        0 => VerificationType::Top,
+        1 => VerificationType::Integer,
+        2 => VerificationType::Float,
+        3 => VerificationType::Double,
+        4 => VerificationType::Long,
+        5 => VerificationType::Null,
+        6 => VerificationType::UninitializedThis,
+        7 => {
+            let index = read_u16(bytes, pos);
+            match constants.get(index as usize) {
+                Some(ClassConstant::Class(name)) => VerificationType::Object(name.clone()),
+                other => panic!("StackMapTable: Object_variable_info does not reference a Class constant: {:?}", other),
+            }
+        }
+        8 => VerificationType::Uninitialized(read_u16(bytes, pos)),
+        other => panic!("StackMapTable: unknown verification_type tag {}", other),
+    }
+}
+
+/// A modeled state is assignable to a declared frame when it has the same
+/// number of entries and each entry is either identical or a subtype per
+/// [`is_assignable`].
+fn assert_assignable(
+    pc: u16,
+    declared: &[VerificationType],
+    actual: &[VerificationType],
+) -> Result<(), VerifyError> {
+    if declared.len() != actual.len() {
+        return Err(VerifyError {
+            pc,
+            expected: declared.first().cloned().unwrap_or(VerificationType::Top),
+            actual: actual.first().cloned().unwrap_or(VerificationType::Top),
+        });
+    }
+
+    for (expected, actual) in declared.iter().zip(actual.iter()) {
+        if !is_assignable(expected, actual) {
+            return Err(VerifyError {
+                pc,
+                expected: expected.clone(),
+                actual: actual.clone(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Whether `actual` can stand in for `expected`. Reference types are
+/// assignable up the (unmodeled here) class hierarchy by name equality or
+/// `Null`; everything else must match exactly, since e.g. `Integer` and
+/// `Float` occupy the same slot width but are not interchangeable.
+fn is_assignable(expected: &VerificationType, actual: &VerificationType) -> bool {
+    match (expected, actual) {
+        (VerificationType::Object(_), VerificationType::Null) => true,
+        (VerificationType::Object(expected_class), VerificationType::Object(actual_class)) => {
+            expected_class == actual_class
+        }
+        (expected, actual) => expected == actual,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use enumset::EnumSet;
+    use model::class::{ClassMethod, Code, MethodAccessFlag, MethodSignature};
+
+    fn method_with(
+        code: Vec<u8>,
+        max_stack: u16,
+        max_locals: u16,
+        frames: Vec<model::class::StackMapFrame>,
+    ) -> ClassMethod {
+        let mut access_flags = EnumSet::new();
+        access_flags.insert(MethodAccessFlag::Static);
+        ClassMethod {
+            access_flags,
+            name: "test".to_string(),
+            descriptor: MethodSignature {
+                parameters: vec![],
+                return_type: TypeSignature::Void,
+            },
+            attributes: vec![
+                ClassAttribute::Code(Code {
+                    max_stack,
+                    max_locals,
+                    code,
+                    exception_table: vec![],
+                    attributes: vec![],
+                }),
+                ClassAttribute::StackMapTable(frames),
+            ],
+        }
+    }
+
+    #[test]
+    fn iconst_then_istore_matches_a_declared_frame() {
+        let class = JvmClass::default();
+        // iconst_0 (3), istore 0 (54, 0), return (177); the frame is declared at the return (pc 3)
+        let code = vec![3, 54, 0, 177];
+        let frame = model::class::StackMapFrame {
+            offset_delta: 3,
+            locals: vec![VerificationType::Integer],
+            stack: vec![],
+        };
+        let method = method_with(code, 1, 1, vec![frame]);
+
+        assert!(verify_method(&class, &method).is_ok());
+    }
+
+    #[test]
+    fn mismatched_declared_frame_is_rejected() {
+        let class = JvmClass::default();
+        let code = vec![3, 54, 0, 177];
+        let frame = model::class::StackMapFrame {
+            offset_delta: 3,
+            locals: vec![VerificationType::Float],
+            stack: vec![],
+        };
+        let method = method_with(code, 1, 1, vec![frame]);
+
+        let error = verify_method(&class, &method).unwrap_err();
+        assert_eq!(error.expected, VerificationType::Float);
+        assert_eq!(error.actual, VerificationType::Integer);
+    }
+
+    #[test]
+    fn stack_overflow_past_max_stack_is_rejected() {
+        let class = JvmClass::default();
+        let code = vec![3, 4, 177]; // iconst_0, iconst_1, return
+        let method = method_with(code, 1, 0, vec![]);
+
+        assert!(verify_method(&class, &method).is_err());
+    }
+
+    #[test]
+    fn decode_stack_map_table_resolves_append_and_chop_against_the_entry_frame() {
+        let constants = vec![ClassConstant::Unused()];
+        let entry_locals = vec![VerificationType::Integer];
+
+        // two entries:
+        // frame_type 252 (APPEND, +1 local); offset_delta 0; one verification_type_info: tag 1 (Integer)
+        // frame_type 250 (CHOP, -1 local); offset_delta 1
+        let bytes = vec![0, 2, 252, 0, 0, 1, 250, 0, 1];
+        let frames = decode_stack_map_table(&bytes, &constants, &entry_locals);
+
+        assert_eq!(frames.len(), 2);
+        assert_eq!(
+            frames[0].locals,
+            vec![VerificationType::Integer, VerificationType::Integer]
+        );
+        assert_eq!(frames[0].stack, vec![]);
+        assert_eq!(frames[1].offset_delta, 1);
+        assert_eq!(frames[1].locals, vec![VerificationType::Integer]);
+    }
+
+    #[test]
+    fn unknown_opcode_is_rejected_instead_of_silently_skipped() {
+        let class = JvmClass::default();
+        let code = vec![253, 177]; // <unknown>, return
+        let method = method_with(code, 0, 0, vec![]);
+
+        assert!(verify_method(&class, &method).is_err());
+    }
+
+    #[test]
+    fn locals_width_counts_wide_types_twice() {
+        let locals = vec![
+            VerificationType::Integer,
+            VerificationType::Long,
+            VerificationType::Object("x".to_string()),
+        ];
+        assert_eq!(locals_width(&locals), 4);
+    }
+}